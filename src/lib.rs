@@ -1,4 +1,5 @@
 mod turtle_board;
+mod turtle_canvas;
 
 use turtle_board::TurtleBoard;
 
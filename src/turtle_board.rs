@@ -1,23 +1,25 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::vec::Vec;
 use std::cmp;
 use std::ops::Range;
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::error::Error;
 
-const DOT: &str = "+";
-const DASH_VER: &str = "|";
-const BLANK_VER: &str = " ";
+pub(crate) const DOT: &str = "+";
+pub(crate) const DASH_VER: &str = "|";
+pub(crate) const BLANK_VER: &str = " ";
 
-const GAP: &str = "    ";
-const DASH_HOR: &str = "----";
-const BLANK_HOR: &str = "    ";
+pub(crate) const GAP: &str = "    ";
+pub(crate) const DASH_HOR: &str = "----";
+pub(crate) const BLANK_HOR: &str = "    ";
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Bounds {
-    min_x: i32,
-    min_y: i32,
-    max_x: i32,
-    max_y: i32,
+    pub(crate) min_x: i32,
+    pub(crate) min_y: i32,
+    pub(crate) max_x: i32,
+    pub(crate) max_y: i32,
 }
 
 impl Bounds {
@@ -69,30 +71,347 @@ impl Edge {
     }
 }
 
+/// A dynamic single-axis mapping from signed lattice coordinates to a
+/// dense, zero-based index range, in the style of the infinite grids used
+/// for Conway-cube style simulations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Dimension {
+    offset: i32,
+    size: u32,
+}
+
+impl Dimension {
+    fn empty() -> Dimension {
+        Dimension { offset: 0, size: 0 }
+    }
+
+    fn map(&self, pos: i32) -> Option<usize> {
+        let idx = self.offset as i64 + pos as i64;
+        if idx >= 0 && idx < self.size as i64 {
+            Some(idx as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Grows this dimension so `pos` becomes in-range. Rather than
+    /// stopping at the exact minimal bound, it over-allocates
+    /// geometrically (doubling, à la `Vec::push`). This keeps repeated
+    /// one-row growth (the common case when a board is drawn
+    /// incrementally) amortized O(1) instead of re-copying the backing
+    /// grid on every touch.
+    fn include(&self, pos: i32) -> Dimension {
+        if self.map(pos).is_some() {
+            return *self;
+        }
+        let cur_left = -self.offset;
+        let cur_right = self.size as i32 - self.offset - 1;
+        let left = cmp::min(pos, cur_left);
+        let right = cmp::max(pos, cur_right);
+        let needed = (right - left + 1) as u32;
+        let grown = cmp::max(self.size.saturating_mul(2), needed).max(1);
+        let extra = (grown - needed) as i32;
+        let (left, right) = if pos < cur_left {
+            (left - extra, right)
+        } else {
+            (left, right + extra)
+        };
+        Dimension {
+            offset: -left,
+            size: (right - left + 1) as u32,
+        }
+    }
+}
+
+/// A dense, dynamically-resizing boolean grid over one axis of edges
+/// (either all horizontal or all vertical edges). Backed by a flat
+/// `Vec<bool>` (one byte per cell, not bit-packed) indexed by
+/// `y_idx * x_dim.size + x_idx`. Growth is geometric (see
+/// `Dimension::include`), so drawing a board
+/// incrementally, one line at a time, reallocates and copies the grid
+/// an amortized O(1) number of times rather than once per line.
+#[derive(Debug, Clone)]
+struct EdgeGrid {
+    x_dim: Dimension,
+    y_dim: Dimension,
+    cells: Vec<bool>,
+}
+
+impl EdgeGrid {
+    fn new() -> EdgeGrid {
+        EdgeGrid {
+            x_dim: Dimension::empty(),
+            y_dim: Dimension::empty(),
+            cells: Vec::new(),
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let x_idx = self.x_dim.map(x)?;
+        let y_idx = self.y_dim.map(y)?;
+        Some(y_idx * self.x_dim.size as usize + x_idx)
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        self.index(x, y).is_some_and(|idx| self.cells[idx])
+    }
+
+    fn reserve(&mut self, x_min: i32, x_max: i32, y_min: i32, y_max: i32) {
+        let new_x_dim = self.x_dim.include(x_min).include(x_max);
+        let new_y_dim = self.y_dim.include(y_min).include(y_max);
+        if new_x_dim == self.x_dim && new_y_dim == self.y_dim {
+            return;
+        }
+
+        let mut new_cells = vec![false; new_x_dim.size as usize * new_y_dim.size as usize];
+        for old_y_idx in 0..self.y_dim.size {
+            for old_x_idx in 0..self.x_dim.size {
+                let old_idx = (old_y_idx * self.x_dim.size + old_x_idx) as usize;
+                if !self.cells[old_idx] {
+                    continue;
+                }
+                let x = old_x_idx as i32 - self.x_dim.offset;
+                let y = old_y_idx as i32 - self.y_dim.offset;
+                let new_x_idx = new_x_dim.map(x).expect("previous x must remain in range");
+                let new_y_idx = new_y_dim.map(y).expect("previous y must remain in range");
+                new_cells[new_y_idx * new_x_dim.size as usize + new_x_idx] = true;
+            }
+        }
+
+        self.x_dim = new_x_dim;
+        self.y_dim = new_y_dim;
+        self.cells = new_cells;
+    }
+
+    fn insert(&mut self, x: i32, y: i32) {
+        self.reserve(x, x, y, y);
+        let idx = self.index(x, y).expect("cell must be in bounds after reserve");
+        self.cells[idx] = true;
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let x_size = self.x_dim.size;
+        (0..self.y_dim.size).flat_map(move |y_idx| {
+            (0..x_size).filter_map(move |x_idx| {
+                let idx = (y_idx * x_size + x_idx) as usize;
+                if self.cells[idx] {
+                    Some((x_idx as i32 - self.x_dim.offset, y_idx as i32 - self.y_dim.offset))
+                } else {
+                    None
+                }
+            })
+        })
+    }
+}
+
+/// The dense backend for `TurtleBoard`: two boolean grids, one per
+/// edge orientation, each growing independently as lines are drawn
+/// outside their current extent.
+#[derive(Debug, Clone)]
+struct DenseEdges {
+    horizontal: EdgeGrid,
+    vertical: EdgeGrid,
+}
+
+impl DenseEdges {
+    fn new() -> DenseEdges {
+        DenseEdges {
+            horizontal: EdgeGrid::new(),
+            vertical: EdgeGrid::new(),
+        }
+    }
+
+    fn insert_horizontal_line(&mut self, xs: Range<i32>, y: i32) {
+        if xs.is_empty() {
+            return;
+        }
+        self.horizontal.reserve(xs.start, xs.end - 1, y, y);
+        for x in xs {
+            self.horizontal.insert(x, y);
+        }
+    }
+
+    fn insert_vertical_line(&mut self, x: i32, ys: Range<i32>) {
+        if ys.is_empty() {
+            return;
+        }
+        self.vertical.reserve(x, x, ys.start, ys.end - 1);
+        for y in ys {
+            self.vertical.insert(x, y);
+        }
+    }
+
+    fn contains_horizontal(&self, x: i32, y: i32) -> bool {
+        self.horizontal.contains(x, y)
+    }
+
+    fn contains_vertical(&self, x: i32, y: i32) -> bool {
+        self.vertical.contains(x, y)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.horizontal.iter().next().is_none() && self.vertical.iter().next().is_none()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Edge> + '_ {
+        let horizontal = self.horizontal.iter().map(|(x, y)| Edge::Horizontal(x, y));
+        let vertical = self.vertical.iter().map(|(x, y)| Edge::Vertical(x, y));
+        horizontal.chain(vertical)
+    }
+}
+
+/// The storage backend behind a `TurtleBoard`: either the original
+/// `HashSet<Edge>` or the dense `DenseEdges` boolean grids. Both backends
+/// expose the same operations so the board can use either one
+/// interchangeably.
+#[derive(Debug, Clone)]
+enum EdgeStorage {
+    Sparse(HashSet<Edge>),
+    Dense(DenseEdges),
+}
+
+impl EdgeStorage {
+    fn sparse() -> EdgeStorage {
+        EdgeStorage::Sparse(HashSet::new())
+    }
+
+    fn dense() -> EdgeStorage {
+        EdgeStorage::Dense(DenseEdges::new())
+    }
+
+    fn insert_horizontal_line(&mut self, xs: Range<i32>, y: i32) {
+        match self {
+            EdgeStorage::Sparse(edges) => {
+                for x in xs {
+                    edges.insert(Edge::Horizontal(x, y));
+                }
+            }
+            EdgeStorage::Dense(dense) => dense.insert_horizontal_line(xs, y),
+        }
+    }
+
+    fn insert_vertical_line(&mut self, x: i32, ys: Range<i32>) {
+        match self {
+            EdgeStorage::Sparse(edges) => {
+                for y in ys {
+                    edges.insert(Edge::Vertical(x, y));
+                }
+            }
+            EdgeStorage::Dense(dense) => dense.insert_vertical_line(x, ys),
+        }
+    }
+
+    fn contains_horizontal(&self, x: i32, y: i32) -> bool {
+        match self {
+            EdgeStorage::Sparse(edges) => edges.contains(&Edge::Horizontal(x, y)),
+            EdgeStorage::Dense(dense) => dense.contains_horizontal(x, y),
+        }
+    }
+
+    fn contains_vertical(&self, x: i32, y: i32) -> bool {
+        match self {
+            EdgeStorage::Sparse(edges) => edges.contains(&Edge::Vertical(x, y)),
+            EdgeStorage::Dense(dense) => dense.contains_vertical(x, y),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            EdgeStorage::Sparse(edges) => edges.is_empty(),
+            EdgeStorage::Dense(dense) => dense.is_empty(),
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        match self {
+            EdgeStorage::Sparse(edges) => Box::new(edges.iter().copied()),
+            EdgeStorage::Dense(dense) => Box::new(dense.iter()),
+        }
+    }
+}
+
+/// A disjoint-set over `0..size`, with path compression and union by
+/// rank, used by `TurtleBoard::enclosed_cells` to group unit cells that
+/// are connected by a missing edge.
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u32>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return;
+        }
+        if self.rank[root_a] < self.rank[root_b] {
+            self.parent[root_a] = root_b;
+        } else if self.rank[root_a] > self.rank[root_b] {
+            self.parent[root_b] = root_a;
+        } else {
+            self.parent[root_b] = root_a;
+            self.rank[root_a] += 1;
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TurtleBoard {
-    edges: HashSet<Edge>,
+    edges: EdgeStorage,
     lazy_bounds: bool,
     bounds: Option<Bounds>,
 }
 
 impl TurtleBoard {
-    pub fn new(lazy_bounds: bool) -> TurtleBoard {
+    fn with_storage(edges: EdgeStorage, lazy_bounds: bool) -> TurtleBoard {
         TurtleBoard {
-            edges: HashSet::new(),
+            edges,
             lazy_bounds,
             bounds: None,
         }
     }
-    
+
+    pub fn new(lazy_bounds: bool) -> TurtleBoard {
+        TurtleBoard::with_storage(EdgeStorage::sparse(), lazy_bounds)
+    }
+
     pub fn new_lazy() -> TurtleBoard {
         TurtleBoard::new(true)
     }
-    
+
     pub fn new_strict() -> TurtleBoard {
         TurtleBoard::new(false)
     }
-    
+
+    /// Like `new`, but backed by dense boolean grids instead of a
+    /// `HashSet`, which is cheaper for large, densely-drawn boards.
+    pub fn new_dense(lazy_bounds: bool) -> TurtleBoard {
+        TurtleBoard::with_storage(EdgeStorage::dense(), lazy_bounds)
+    }
+
+    pub fn new_dense_lazy() -> TurtleBoard {
+        TurtleBoard::new_dense(true)
+    }
+
+    pub fn new_dense_strict() -> TurtleBoard {
+        TurtleBoard::new_dense(false)
+    }
+
     pub fn add_vertical_line(&mut self, x: i32, ys: Range<i32>) {
         self.expand_to_fit(Bounds {
             min_x: x,
@@ -100,11 +419,9 @@ impl TurtleBoard {
             min_y: ys.start,
             max_y: ys.end,
         });
-        for y in ys {
-            self.edges.insert(Edge::Vertical(x, y));
-        }
+        self.edges.insert_vertical_line(x, ys);
     }
-    
+
     pub fn add_horizontal_line(&mut self, xs: Range<i32>, y: i32) {
         self.expand_to_fit(Bounds {
             min_y: y,
@@ -112,25 +429,144 @@ impl TurtleBoard {
             min_x: xs.start,
             max_x: xs.end,
         });
-        for x in xs {
-            self.edges.insert(Edge::Horizontal(x, y));
-        }
+        self.edges.insert_horizontal_line(xs, y);
     }
-    
+
     pub fn contains_vertical_line(&self, x: i32, ys: Range<i32>) -> bool {
         return ys
-            .filter(|y| !self.edges.contains(&Edge::Vertical(x, *y)))
+            .filter(|y| !self.edges.contains_vertical(x, *y))
             .next()
             .is_none();
     }
-    
+
     pub fn contains_horizontal_line(&self, xs: Range<i32>, y: i32) -> bool {
         return xs
-            .filter(|x| !self.edges.contains(&Edge::Horizontal(*x, y)))
+            .filter(|x| !self.edges.contains_horizontal(*x, y))
             .next()
             .is_none();
     }
-    
+
+    /// Returns the unit cells (identified by their bottom-left corner)
+    /// that are fully walled off from the exterior by the drawn edges.
+    ///
+    /// Builds a union-find over every cell in `bounds_uncached()` plus a
+    /// sentinel "outside" node, unioning adjacent cells whenever the edge
+    /// between them is missing and unioning border cells with missing
+    /// border edges directly into the outside node. Any cell whose root
+    /// differs from the outside node's root is enclosed.
+    pub fn enclosed_cells(&self) -> HashSet<(i32, i32)> {
+        if self.edges.is_empty() {
+            return HashSet::new();
+        }
+        let bounds = self.bounds_uncached();
+        let width = (bounds.max_x - bounds.min_x) as usize;
+        let height = (bounds.max_y - bounds.min_y) as usize;
+        if width == 0 || height == 0 {
+            return HashSet::new();
+        }
+
+        let cell_index = |x: i32, y: i32| -> usize {
+            (y - bounds.min_y) as usize * width + (x - bounds.min_x) as usize
+        };
+        let outside = width * height;
+        let mut uf = UnionFind::new(width * height + 1);
+
+        for y in bounds.min_y..bounds.max_y {
+            for x in bounds.min_x..bounds.max_x {
+                let idx = cell_index(x, y);
+
+                if x == bounds.min_x && !self.edges.contains_vertical(x, y) {
+                    uf.union(idx, outside);
+                }
+                if y == bounds.min_y && !self.edges.contains_horizontal(x, y) {
+                    uf.union(idx, outside);
+                }
+
+                if !self.edges.contains_vertical(x + 1, y) {
+                    if x + 1 < bounds.max_x {
+                        uf.union(idx, cell_index(x + 1, y));
+                    } else {
+                        uf.union(idx, outside);
+                    }
+                }
+
+                if !self.edges.contains_horizontal(x, y + 1) {
+                    if y + 1 < bounds.max_y {
+                        uf.union(idx, cell_index(x, y + 1));
+                    } else {
+                        uf.union(idx, outside);
+                    }
+                }
+            }
+        }
+
+        let outside_root = uf.find(outside);
+        let mut enclosed = HashSet::new();
+        for y in bounds.min_y..bounds.max_y {
+            for x in bounds.min_x..bounds.max_x {
+                if uf.find(cell_index(x, y)) != outside_root {
+                    enclosed.insert((x, y));
+                }
+            }
+        }
+        enclosed
+    }
+
+    /// Returns the minimum-hop path of lattice vertices from `start` to
+    /// `end` following only drawn edges, or `None` if they aren't
+    /// connected. Since every edge is unit length, a breadth-first
+    /// search yields a shortest path.
+    pub fn shortest_path(&self, start: (i32, i32), end: (i32, i32)) -> Option<Vec<(i32, i32)>> {
+        if start == end {
+            return Some(vec![start]);
+        }
+
+        let mut predecessors: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut queue: VecDeque<(i32, i32)> = VecDeque::new();
+        predecessors.insert(start, start);
+        queue.push_back(start);
+
+        while let Some(vertex) = queue.pop_front() {
+            if vertex == end {
+                break;
+            }
+            for neighbor in self.connected_vertices(vertex) {
+                if let std::collections::hash_map::Entry::Vacant(entry) = predecessors.entry(neighbor) {
+                    entry.insert(vertex);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        if !predecessors.contains_key(&end) {
+            return None;
+        }
+
+        let mut path = vec![end];
+        while *path.last().unwrap() != start {
+            path.push(predecessors[path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    fn connected_vertices(&self, (x, y): (i32, i32)) -> Vec<(i32, i32)> {
+        let mut neighbors = Vec::new();
+        if self.edges.contains_vertical(x, y) {
+            neighbors.push((x, y + 1));
+        }
+        if self.edges.contains_vertical(x, y - 1) {
+            neighbors.push((x, y - 1));
+        }
+        if self.edges.contains_horizontal(x, y) {
+            neighbors.push((x + 1, y));
+        }
+        if self.edges.contains_horizontal(x - 1, y) {
+            neighbors.push((x - 1, y));
+        }
+        neighbors
+    }
+
     fn expand_to_fit(&mut self, bounds_to_fit: Bounds) {
         self.bounds = if self.lazy_bounds {
             None
@@ -140,7 +576,7 @@ impl TurtleBoard {
             Some(Bounds::min_bound_2_bounds(&bounds_to_fit, self.bounds()))
         }
     }
-    
+
     fn compute_bounds(&self) -> Bounds {
         let bounds: Vec<_> = self.edges
             .iter()
@@ -148,14 +584,14 @@ impl TurtleBoard {
             .collect();
         Bounds::min_bound(&bounds[..])
     }
-    
+
     pub fn bounds_uncached(&self) -> Bounds {
         match &self.bounds {
             Some(bounds) => bounds.clone(),
             None => self.compute_bounds()
         }
     }
-    
+
     pub fn bounds(&mut self) -> &Bounds {
         if self.bounds.is_none() {
             self.bounds = Some(self.compute_bounds());
@@ -166,36 +602,40 @@ impl TurtleBoard {
 
 impl PartialEq for TurtleBoard {
     fn eq(&self, other: &TurtleBoard) -> bool {
-        self.edges == other.edges
+        let self_edges: HashSet<Edge> = self.edges.iter().collect();
+        let other_edges: HashSet<Edge> = other.edges.iter().collect();
+        self_edges == other_edges
     }
 }
 
 impl Eq for TurtleBoard {
 }
 
-impl Display for TurtleBoard {
-    fn fmt(&self, formatter: &mut Formatter) -> Result<(), fmt::Error> {
-        let bounds = self.bounds_uncached();
-        
-        let draw_line_hor = |formatter: &mut Formatter, y: i32| -> Result<(), fmt::Error> {
-            write!(formatter, "{}", DOT)?;
+impl TurtleBoard {
+    /// Draws the portion of the board covered by `bounds` into `writer`,
+    /// in the same dot/dash/gap layout as `Display`. Edges outside
+    /// `bounds` are simply never visited, so a line that runs past the
+    /// edge of `bounds` is clipped there.
+    fn render_into(&self, writer: &mut dyn fmt::Write, bounds: &Bounds) -> Result<(), fmt::Error> {
+        let draw_line_hor = |writer: &mut dyn fmt::Write, y: i32| -> Result<(), fmt::Error> {
+            write!(writer, "{}", DOT)?;
             for x in bounds.min_x..bounds.max_x {
-                write!(formatter, "{}",
-                    if self.edges.contains(&Edge::Horizontal(x, y)) {
+                write!(writer, "{}",
+                    if self.edges.contains_horizontal(x, y) {
                         DASH_HOR
                     } else {
                         BLANK_HOR
                     }
                 )?;
-                write!(formatter, "{}", DOT)?;
+                write!(writer, "{}", DOT)?;
             }
             Ok(())
         };
-        
-        let draw_line_ver = |formatter: &mut Formatter, y: i32| -> Result<(), fmt::Error> {
-            let add_ver = |formatter: &mut Formatter, x: i32| -> Result<(), fmt::Error> {
-                write!(formatter, "{}",
-                    if self.edges.contains(&Edge::Vertical(x, y)) {
+
+        let draw_line_ver = |writer: &mut dyn fmt::Write, y: i32| -> Result<(), fmt::Error> {
+            let add_ver = |writer: &mut dyn fmt::Write, x: i32| -> Result<(), fmt::Error> {
+                write!(writer, "{}",
+                    if self.edges.contains_vertical(x, y) {
                         DASH_VER
                     } else {
                         BLANK_VER
@@ -203,24 +643,234 @@ impl Display for TurtleBoard {
                 )?;
                 Ok(())
             };
-        
+
             for x in bounds.min_x..bounds.max_x {
-                add_ver(formatter, x)?;
-                write!(formatter, "{}", GAP)?;
+                add_ver(writer, x)?;
+                write!(writer, "{}", GAP)?;
             }
-            add_ver(formatter, bounds.max_x)?;
+            add_ver(writer, bounds.max_x)?;
             Ok(())
         };
-        
-        draw_line_hor(formatter, bounds.max_y)?;
+
+        draw_line_hor(writer, bounds.max_y)?;
         for y in (bounds.min_y..bounds.max_y).rev() {
-            write!(formatter, "\n")?;
-            draw_line_ver(formatter, y)?;
-            write!(formatter, "\n")?;
-            draw_line_hor(formatter, y)?;
+            write!(writer, "\n")?;
+            draw_line_ver(writer, y)?;
+            write!(writer, "\n")?;
+            draw_line_hor(writer, y)?;
         }
         Ok(())
     }
+
+    /// Renders just the slice of the board covered by `window`, without
+    /// materializing the full grid. Useful for inspecting a large or
+    /// lazily-bounded board incrementally, one viewport at a time.
+    pub fn render_region(&self, window: &Bounds) -> String {
+        let mut output = String::new();
+        self.render_into(&mut output, window)
+            .expect("writing to a String cannot fail");
+        output
+    }
+}
+
+impl Display for TurtleBoard {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), fmt::Error> {
+        self.render_into(formatter, &self.bounds_uncached())
+    }
+}
+
+/// Why `TurtleBoard::parse_ascii` (and the `FromStr` impl built on it)
+/// couldn't reconstruct a board from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTurtleBoardError {
+    /// The input had no lines at all.
+    EmptyInput,
+    /// The number of lines can't be split into alternating dot/edge rows.
+    WrongLineCount { found: usize },
+    /// The first line's length isn't that of a dot row (one dot followed
+    /// by whole dash-and-dot segments).
+    WrongRowWidth { line: usize, expected: usize, found: usize },
+    /// A line's length didn't match the column count implied by the
+    /// first line.
+    InconsistentColumnCount { line: usize, expected: usize, found: usize },
+    /// A byte that isn't one of the `Display` glyphs showed up where a
+    /// dot, dash, or blank was expected.
+    UnexpectedGlyph { line: usize, column: usize, found: char },
+}
+
+impl Display for ParseTurtleBoardError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseTurtleBoardError::EmptyInput =>
+                write!(formatter, "input was empty"),
+            ParseTurtleBoardError::WrongLineCount { found } =>
+                write!(formatter, "expected an odd number of lines, found {}", found),
+            ParseTurtleBoardError::WrongRowWidth { line, expected, found } =>
+                write!(formatter, "line {}: expected {} characters, found {}", line, expected, found),
+            ParseTurtleBoardError::InconsistentColumnCount { line, expected, found } =>
+                write!(formatter, "line {}: expected {} columns, found {}", line, expected, found),
+            ParseTurtleBoardError::UnexpectedGlyph { line, column, found } =>
+                write!(formatter, "line {}, column {}: unexpected character {:?}", line, column, found),
+        }
+    }
+}
+
+impl Error for ParseTurtleBoardError {
+}
+
+impl TurtleBoard {
+    /// Parses the exact text that `Display` emits back into a
+    /// `TurtleBoard`, anchoring the reconstructed board's bottom-left
+    /// corner at the origin.
+    ///
+    /// `Display`'s grid of dots and dashes carries no record of where
+    /// the original board's bounds sat on the lattice, only their
+    /// width and height, so the parsed board is always re-anchored at
+    /// `(0, 0)`. This means `parse_ascii(&format!("{}", board))`
+    /// reproduces `board`'s *shape* exactly, but only equals `board`
+    /// itself (via `PartialEq`, which compares raw edge coordinates)
+    /// when `board` was already anchored at the origin. A board drawn
+    /// with negative coordinates, for instance, round-trips to a copy
+    /// shifted to start at `(0, 0)`. Callers who still have the
+    /// original bottom-left corner on hand (e.g. `board.bounds_uncached()`,
+    /// recorded before the board was serialized) can recover an exact
+    /// round trip with `parse_ascii_at`.
+    pub fn parse_ascii(s: &str) -> Result<TurtleBoard, ParseTurtleBoardError> {
+        TurtleBoard::parse_ascii_at(s, (0, 0))
+    }
+
+    /// Like `parse_ascii`, but anchors the reconstructed board's
+    /// bottom-left corner at `origin` instead of `(0, 0)`.
+    ///
+    /// `Display`'s text carries no position information of its own, so
+    /// reproducing a board exactly (rather than just its shape) requires
+    /// the caller to supply the original bottom-left corner separately,
+    /// e.g. `TurtleBoard::parse_ascii_at(&text, (board.bounds_uncached().min_x,
+    /// board.bounds_uncached().min_y))` round-trips `board` exactly.
+    pub fn parse_ascii_at(s: &str, origin: (i32, i32)) -> Result<TurtleBoard, ParseTurtleBoardError> {
+        let lines: Vec<&str> = s.lines().collect();
+        if lines.is_empty() {
+            return Err(ParseTurtleBoardError::EmptyInput);
+        }
+        if lines.len().is_multiple_of(2) {
+            return Err(ParseTurtleBoardError::WrongLineCount { found: lines.len() });
+        }
+
+        let row_width = lines[0].len();
+        let segment_len = DOT.len() + DASH_HOR.len();
+        if row_width < DOT.len() || !(row_width - DOT.len()).is_multiple_of(segment_len) {
+            let expected = DOT.len() + ((row_width.saturating_sub(DOT.len())) / segment_len) * segment_len;
+            return Err(ParseTurtleBoardError::WrongRowWidth {
+                line: 0,
+                expected,
+                found: row_width,
+            });
+        }
+        let width = (row_width - DOT.len()) / segment_len;
+        let height = (lines.len() - 1) / 2;
+        let (origin_x, origin_y) = origin;
+
+        let mut board = TurtleBoard::new_strict();
+        for (line_index, line) in lines.iter().enumerate() {
+            if line_index % 2 == 0 {
+                let y = origin_y + height as i32 - (line_index / 2) as i32;
+                parse_dot_row(line, line_index, origin_x, y, width, &mut board)?;
+            } else {
+                let y = origin_y + height as i32 - 1 - (line_index / 2) as i32;
+                parse_edge_row(line, line_index, origin_x, y, width, &mut board)?;
+            }
+        }
+        Ok(board)
+    }
+}
+
+impl FromStr for TurtleBoard {
+    type Err = ParseTurtleBoardError;
+
+    fn from_str(s: &str) -> Result<TurtleBoard, ParseTurtleBoardError> {
+        TurtleBoard::parse_ascii(s)
+    }
+}
+
+fn expect_glyph(
+    line: &str,
+    line_index: usize,
+    column: usize,
+    candidates: &[(&str, bool)],
+) -> Result<bool, ParseTurtleBoardError> {
+    for (glyph, value) in candidates {
+        if line[column..].starts_with(glyph) {
+            return Ok(*value);
+        }
+    }
+    let found = line[column..].chars().next().unwrap_or('\0');
+    Err(ParseTurtleBoardError::UnexpectedGlyph { line: line_index, column, found })
+}
+
+fn parse_dot_row(
+    line: &str,
+    line_index: usize,
+    origin_x: i32,
+    y: i32,
+    width: usize,
+    board: &mut TurtleBoard,
+) -> Result<(), ParseTurtleBoardError> {
+    let segment_len = DASH_HOR.len() + DOT.len();
+    let expected_len = DOT.len() + width * segment_len;
+    if line.len() != expected_len {
+        return Err(ParseTurtleBoardError::InconsistentColumnCount {
+            line: line_index,
+            expected: width,
+            found: line.len().saturating_sub(DOT.len()).div_ceil(segment_len),
+        });
+    }
+
+    expect_glyph(line, line_index, 0, &[(DOT, true)])?;
+    let mut column = DOT.len();
+    for x in 0..width {
+        let has_edge = expect_glyph(line, line_index, column, &[(DASH_HOR, true), (BLANK_HOR, false)])?;
+        if has_edge {
+            let x = origin_x + x as i32;
+            board.add_horizontal_line(x..(x + 1), y);
+        }
+        column += DASH_HOR.len();
+        expect_glyph(line, line_index, column, &[(DOT, true)])?;
+        column += DOT.len();
+    }
+    Ok(())
+}
+
+fn parse_edge_row(
+    line: &str,
+    line_index: usize,
+    origin_x: i32,
+    y: i32,
+    width: usize,
+    board: &mut TurtleBoard,
+) -> Result<(), ParseTurtleBoardError> {
+    let segment_len = DASH_VER.len() + GAP.len();
+    let expected_len = width * segment_len + DASH_VER.len();
+    if line.len() != expected_len {
+        return Err(ParseTurtleBoardError::InconsistentColumnCount {
+            line: line_index,
+            expected: width,
+            found: line.len().saturating_sub(DASH_VER.len()).div_ceil(segment_len),
+        });
+    }
+
+    let mut column = 0;
+    for x in 0..=width {
+        let has_edge = expect_glyph(line, line_index, column, &[(DASH_VER, true), (BLANK_VER, false)])?;
+        if has_edge {
+            board.add_vertical_line(origin_x + x as i32, y..(y + 1));
+        }
+        column += DASH_VER.len();
+        if x < width {
+            expect_glyph(line, line_index, column, &[(GAP, true)])?;
+            column += GAP.len();
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -245,67 +895,107 @@ mod tests {
             edges.insert(Edge::Vertical(3, y));
         }
         
-        assert_eq!(edges, board.edges);
+        assert_eq!(edges, board.edges.iter().collect::<HashSet<_>>());
     }
-    
+
     #[test]
-    fn contains_lines() {
-        let mut board = TurtleBoard::new_lazy();
+    fn dense_adding_lines() {
+        let mut board = TurtleBoard::new_dense_lazy();
+        board.add_horizontal_line(-3..5, 2);
+        board.add_horizontal_line(-2..0, -1);
+        board.add_vertical_line(3, -12..19);
+
+        let mut edges: HashSet<Edge> = HashSet::new();
+        for x in -3..5 {
+            edges.insert(Edge::Horizontal(x, 2));
+        }
+        for x in -2..0 {
+            edges.insert(Edge::Horizontal(x, -1));
+        }
+        for y in -12..19 {
+            edges.insert(Edge::Vertical(3, y));
+        }
+
+        assert_eq!(edges, board.edges.iter().collect::<HashSet<_>>());
+    }
+
+    fn test_contains_lines(new_board: fn(bool) -> TurtleBoard) {
+        let mut board = new_board(true);
         board.add_horizontal_line(-3..5, 2);
         board.add_horizontal_line(-2..0, -1);
         board.add_vertical_line(3, -12..19);
         board.add_vertical_line(4, -1..2);
-        
+
         assert!(board.contains_horizontal_line(-1..2, 2));
         assert!(board.contains_horizontal_line(-2..0, -1));
         assert!(board.contains_vertical_line(3, -7..19));
         assert!(board.contains_vertical_line(92, -56..-56));
-        
+
         assert!(!board.contains_horizontal_line(-4..5, 2));
         assert!(!board.contains_horizontal_line(-3..6, 2));
         assert!(!board.contains_horizontal_line(3..4, 0));
         assert!(!board.contains_vertical_line(2, -12..19));
     }
-    
-    fn test_bounds(is_lazy: bool) {
-        let mut board = TurtleBoard::new(is_lazy);
-        
+
+    #[test]
+    fn contains_lines() {
+        test_contains_lines(TurtleBoard::new);
+    }
+
+    #[test]
+    fn dense_contains_lines() {
+        test_contains_lines(TurtleBoard::new_dense);
+    }
+
+    fn test_bounds(new_board: fn(bool) -> TurtleBoard, is_lazy: bool) {
+        let mut board = new_board(is_lazy);
+
         board.add_horizontal_line(-1..2, 3);
         let expected_bounds = Bounds {min_x: -1, max_x: 2, min_y: 3, max_y: 3};
         assert_eq!(&expected_bounds, board.bounds());
-        
+
         board.add_horizontal_line(3..5, -2);
         board.add_horizontal_line(1..2, 0);
         let expected_bounds = Bounds {min_x: -1, max_x: 5, min_y: -2, max_y: 3};
         assert_eq!(&expected_bounds, board.bounds());
-        
-        let mut board = TurtleBoard::new(is_lazy);
+
+        let mut board = new_board(is_lazy);
         board.add_vertical_line(-3, 4..7);
         let expected_bounds = Bounds {min_x: -3, max_x: -3, min_y: 4, max_y: 7};
         assert_eq!(&expected_bounds, board.bounds());
-        
+
         board.add_horizontal_line(-12..-6, -20);
         board.add_vertical_line(72, -3..6);
         let expected_bounds = Bounds {min_x: -12, max_x: 72, min_y: -20, max_y: 7};
         assert_eq!(&expected_bounds, board.bounds());
-        
-        let mut board = TurtleBoard::new(is_lazy);
+
+        let mut board = new_board(is_lazy);
         board.add_horizontal_line(5..15, 2);
         board.add_vertical_line(8, 17..19);
         let expected_bounds = Bounds {min_x: 5, max_x: 15, min_y: 2, max_y: 19};
         assert_eq!(&expected_bounds, board.bounds());
     }
-    
+
     #[test]
     fn lazy_bounds() {
-        test_bounds(true);
+        test_bounds(TurtleBoard::new, true);
     }
-    
+
     #[test]
     fn strict_bounds() {
-        test_bounds(false);
+        test_bounds(TurtleBoard::new, false);
     }
-    
+
+    #[test]
+    fn dense_lazy_bounds() {
+        test_bounds(TurtleBoard::new_dense, true);
+    }
+
+    #[test]
+    fn dense_strict_bounds() {
+        test_bounds(TurtleBoard::new_dense, false);
+    }
+
     fn test_switch_bounds(initial_lazy: bool) {
         let mut board = TurtleBoard::new(initial_lazy);
         board.add_horizontal_line(-3..5, 2);
@@ -420,4 +1110,256 @@ mod tests {
         println!("Expected:\n{}\n\nBoard:\n{}", expected_display, board);
         assert_eq!(expected_display, display);
     }
+
+    #[test]
+    fn dense_display() {
+        let mut board = TurtleBoard::new_dense_strict();
+        board.add_horizontal_line(-2..6, 2);
+        board.add_vertical_line(3, 4..6);
+        let display = format!("{}", board);
+        let expected_display =
+"\
+*'*'*'*'*'*'*'*'*
+. . . . . | . . .
+*'*'*'*'*'*'*'*'*
+. . . . . | . . .
+*'*'*'*'*'*'*'*'*
+. . . . . . . . .
+*'*'*'*'*'*'*'*'*
+. . . . . . . . .
+*-*-*-*-*-*-*-*-*"
+            .replace(" ", GAP)
+            .replace("-", DASH_HOR)
+            .replace("|", DASH_VER)
+            .replace("'", BLANK_HOR)
+            .replace(".", BLANK_VER)
+            .replace("*", DOT);
+        println!("Expected:\n{}\n\nBoard:\n{}", expected_display, board);
+        assert_eq!(expected_display, display);
+    }
+
+    #[test]
+    fn enclosed_cells_on_an_empty_board_is_empty() {
+        assert_eq!(HashSet::new(), TurtleBoard::new_lazy().enclosed_cells());
+        assert_eq!(HashSet::new(), TurtleBoard::new_strict().enclosed_cells());
+    }
+
+    #[test]
+    fn enclosed_cells_finds_a_closed_box() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..3, 0);
+        board.add_horizontal_line(0..3, 3);
+        board.add_vertical_line(0, 0..3);
+        board.add_vertical_line(3, 0..3);
+        // leave a gap in the middle wall so the two halves stay connected
+        board.add_vertical_line(1, 0..1);
+        board.add_vertical_line(1, 2..3);
+
+        let mut expected = HashSet::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                expected.insert((x, y));
+            }
+        }
+        assert_eq!(expected, board.enclosed_cells());
+    }
+
+    #[test]
+    fn enclosed_cells_excludes_cells_with_a_gap_in_the_wall() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..3, 0);
+        board.add_horizontal_line(0..3, 3);
+        board.add_vertical_line(0, 0..3);
+        board.add_vertical_line(3, 1..3);
+        // no vertical edge at x=3, y=0: the box leaks out on the right
+
+        assert_eq!(HashSet::new(), board.enclosed_cells());
+    }
+
+    #[test]
+    fn parse_ascii_round_trips_display() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..6, 2);
+        board.add_vertical_line(3, 0..2);
+        let text = format!("{}", board);
+        assert_eq!(board, TurtleBoard::parse_ascii(&text).unwrap());
+        assert_eq!(board, text.parse().unwrap());
+
+        let mut board = TurtleBoard::new_strict();
+        board.add_vertical_line(0, 0..3);
+        board.add_horizontal_line(0..4, 1);
+        board.add_vertical_line(2, 0..4);
+        let text = format!("{}", board);
+        assert_eq!(board, TurtleBoard::parse_ascii(&text).unwrap());
+
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..1, 0);
+        let text = format!("{}", board);
+        assert_eq!(board, TurtleBoard::parse_ascii(&text).unwrap());
+    }
+
+    #[test]
+    fn parse_ascii_round_trips_shape_but_not_position_off_origin() {
+        // `Display` only records a board's width and height, not where
+        // its bounds sat on the lattice, so a board anchored away from
+        // the origin (here, using negative coordinates) round-trips to
+        // a copy shifted to start at (0, 0) rather than to itself.
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(-3..3, -2);
+        board.add_horizontal_line(-3..3, 1);
+        board.add_vertical_line(-1, -2..1);
+        let text = format!("{}", board);
+        let parsed = TurtleBoard::parse_ascii(&text).unwrap();
+
+        assert_ne!(board, parsed);
+
+        let mut shifted = TurtleBoard::new_strict();
+        shifted.add_horizontal_line(0..6, 0);
+        shifted.add_horizontal_line(0..6, 3);
+        shifted.add_vertical_line(2, 0..3);
+        assert_eq!(shifted, parsed);
+    }
+
+    #[test]
+    fn parse_ascii_at_round_trips_a_board_off_the_origin_exactly() {
+        // Supplying the original bottom-left corner lets the caller
+        // recover the board exactly, not just its shape.
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(-3..3, -2);
+        board.add_horizontal_line(-3..3, 1);
+        board.add_vertical_line(-1, -2..1);
+        let bounds = board.bounds_uncached();
+        let text = format!("{}", board);
+
+        let parsed = TurtleBoard::parse_ascii_at(&text, (bounds.min_x, bounds.min_y)).unwrap();
+        assert_eq!(board, parsed);
+    }
+
+    #[test]
+    fn parse_ascii_rejects_empty_input() {
+        assert_eq!(
+            Err(ParseTurtleBoardError::EmptyInput),
+            TurtleBoard::parse_ascii(""),
+        );
+    }
+
+    #[test]
+    fn parse_ascii_rejects_even_line_count() {
+        let result = TurtleBoard::parse_ascii("+-+\n| |\n+-+\n| |");
+        assert_eq!(
+            Err(ParseTurtleBoardError::WrongLineCount { found: 4 }),
+            result,
+        );
+    }
+
+    #[test]
+    fn parse_ascii_rejects_inconsistent_row_width() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..2, 1);
+        board.add_vertical_line(0, 0..1);
+        let mut lines: Vec<String> = format!("{}", board).lines().map(String::from).collect();
+        lines[1].push('!');
+        let text = lines.join("\n");
+
+        assert_eq!(
+            Err(ParseTurtleBoardError::InconsistentColumnCount { line: 1, expected: 2, found: 3 }),
+            TurtleBoard::parse_ascii(&text),
+        );
+    }
+
+    #[test]
+    fn parse_ascii_rejects_unexpected_glyphs() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..2, 0);
+        let mut text: Vec<u8> = format!("{}", board).into_bytes();
+        let bad_index = text.iter().position(|&b| b == b'-').unwrap();
+        text[bad_index] = b'?';
+        let text = String::from_utf8(text).unwrap();
+
+        assert_eq!(
+            Err(ParseTurtleBoardError::UnexpectedGlyph { line: 0, column: bad_index, found: '?' }),
+            TurtleBoard::parse_ascii(&text),
+        );
+    }
+
+    #[test]
+    fn render_region_matches_display_for_the_full_bounds() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(-2..6, 2);
+        board.add_vertical_line(3, 4..6);
+
+        assert_eq!(format!("{}", board), board.render_region(&board.bounds_uncached()));
+    }
+
+    #[test]
+    fn render_region_clips_to_a_sub_window() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(-2..6, 2);
+        board.add_vertical_line(3, 4..6);
+
+        let window = Bounds {min_x: 2, max_x: 4, min_y: 4, max_y: 6};
+        let region = board.render_region(&window);
+        let expected =
+"\
+*'*'*
+. | .
+*'*'*
+. | .
+*'*'*"
+            .replace(" ", GAP)
+            .replace("-", DASH_HOR)
+            .replace("|", DASH_VER)
+            .replace("'", BLANK_HOR)
+            .replace(".", BLANK_VER)
+            .replace("*", DOT);
+        assert_eq!(expected, region);
+    }
+
+    #[test]
+    fn shortest_path_same_vertex() {
+        let mut board = TurtleBoard::new_lazy();
+        board.add_horizontal_line(0..3, 0);
+        assert_eq!(Some(vec![(1, 0)]), board.shortest_path((1, 0), (1, 0)));
+    }
+
+    #[test]
+    fn shortest_path_follows_drawn_edges() {
+        let mut board = TurtleBoard::new_lazy();
+        board.add_horizontal_line(0..3, 0);
+        board.add_vertical_line(3, 0..2);
+        board.add_horizontal_line(0..3, 2);
+
+        let path = board.shortest_path((0, 0), (3, 2)).unwrap();
+        assert_eq!((0, 0), path[0]);
+        assert_eq!((3, 2), *path.last().unwrap());
+        for window in path.windows(2) {
+            let (x1, y1) = window[0];
+            let (x2, y2) = window[1];
+            assert_eq!(1, (x1 - x2).abs() + (y1 - y2).abs());
+        }
+        assert_eq!(6, path.len());
+    }
+
+    #[test]
+    fn shortest_path_takes_the_shorter_of_two_routes() {
+        let mut board = TurtleBoard::new_lazy();
+        // a loop around a 2x1 rectangle split by a middle wall: the bottom
+        // edge (2 hops) is shorter than going over the top (4 hops)
+        board.add_horizontal_line(0..2, 0);
+        board.add_horizontal_line(0..2, 1);
+        board.add_vertical_line(0, 0..1);
+        board.add_vertical_line(2, 0..1);
+        board.add_vertical_line(1, 0..1);
+
+        let path = board.shortest_path((0, 0), (2, 0)).unwrap();
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0)], path);
+    }
+
+    #[test]
+    fn shortest_path_returns_none_when_disconnected() {
+        let mut board = TurtleBoard::new_lazy();
+        board.add_horizontal_line(0..1, 0);
+        board.add_horizontal_line(5..6, 5);
+        assert_eq!(None, board.shortest_path((0, 0), (5, 5)));
+    }
 }
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::turtle_board::{TurtleBoard, BLANK_HOR, BLANK_VER, DASH_HOR, DASH_VER, DOT, GAP};
+
+/// A foreground color for `TurtleCanvas` cell rendering, using the
+/// standard 8 ANSI terminal colors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl AnsiColor {
+    fn code(&self) -> u8 {
+        match self {
+            AnsiColor::Black => 30,
+            AnsiColor::Red => 31,
+            AnsiColor::Green => 32,
+            AnsiColor::Yellow => 33,
+            AnsiColor::Blue => 34,
+            AnsiColor::Magenta => 35,
+            AnsiColor::Cyan => 36,
+            AnsiColor::White => 37,
+        }
+    }
+}
+
+/// What a `TurtleCanvas` overlay value looks like when rendered: a
+/// single glyph drawn in the cell's interior, and an optional color to
+/// wrap it in with ANSI escape codes.
+pub trait CellGlyph {
+    fn glyph(&self) -> char;
+
+    fn color(&self) -> Option<AnsiColor> {
+        None
+    }
+}
+
+/// Pairs a `TurtleBoard`'s edges with a sparse overlay of per-cell data
+/// (a turtle marker, region labels, colors, ...), so the board can carry
+/// typed state alongside the lines it draws.
+#[derive(Debug, Clone)]
+pub struct TurtleCanvas<T: Clone> {
+    board: TurtleBoard,
+    cells: HashMap<(i32, i32), T>,
+}
+
+impl<T: Clone> TurtleCanvas<T> {
+    pub fn new(board: TurtleBoard) -> TurtleCanvas<T> {
+        TurtleCanvas {
+            board,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn board(&self) -> &TurtleBoard {
+        &self.board
+    }
+
+    pub fn board_mut(&mut self) -> &mut TurtleBoard {
+        &mut self.board
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&T> {
+        self.cells.get(&(x, y))
+    }
+
+    pub fn get_mut(&mut self, x: i32, y: i32) -> Option<&mut T> {
+        self.cells.get_mut(&(x, y))
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, value: T) {
+        self.cells.insert((x, y), value);
+    }
+}
+
+impl<T: CellGlyph + Clone> TurtleCanvas<T> {
+    /// Renders the board in the same dot/dash/gap layout as
+    /// `TurtleBoard`'s `Display`, but fills each cell's interior with its
+    /// overlay glyph (falling back to a blank gap) and wraps colored
+    /// cells in ANSI escape codes.
+    pub fn render(&self) -> String {
+        let bounds = self.board.bounds_uncached();
+
+        let draw_line_hor = |output: &mut String, y: i32| {
+            output.push_str(DOT);
+            for x in bounds.min_x..bounds.max_x {
+                output.push_str(if self.board.contains_horizontal_line(x..x + 1, y) {
+                    DASH_HOR
+                } else {
+                    BLANK_HOR
+                });
+                output.push_str(DOT);
+            }
+        };
+
+        let draw_line_ver = |output: &mut String, y: i32| {
+            for x in bounds.min_x..bounds.max_x {
+                output.push_str(if self.board.contains_vertical_line(x, y..y + 1) {
+                    DASH_VER
+                } else {
+                    BLANK_VER
+                });
+                output.push_str(&self.render_cell(x, y));
+            }
+            output.push_str(if self.board.contains_vertical_line(bounds.max_x, y..y + 1) {
+                DASH_VER
+            } else {
+                BLANK_VER
+            });
+        };
+
+        let mut output = String::new();
+        draw_line_hor(&mut output, bounds.max_y);
+        for y in (bounds.min_y..bounds.max_y).rev() {
+            output.push('\n');
+            draw_line_ver(&mut output, y);
+            output.push('\n');
+            draw_line_hor(&mut output, y);
+        }
+        output
+    }
+
+    fn render_cell(&self, x: i32, y: i32) -> String {
+        match self.cells.get(&(x, y)) {
+            None => GAP.to_string(),
+            Some(value) => {
+                let mut filled: String = GAP.chars().collect();
+                filled.replace_range(0..1, &value.glyph().to_string());
+                match value.color() {
+                    Some(color) => format!("\x1b[{}m{}\x1b[0m", color.code(), filled),
+                    None => filled,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_set_and_get_mut() {
+        let mut canvas: TurtleCanvas<&str> = TurtleCanvas::new(TurtleBoard::new_lazy());
+        assert_eq!(None, canvas.get(1, 2));
+
+        canvas.set(1, 2, "turtle");
+        assert_eq!(Some(&"turtle"), canvas.get(1, 2));
+        assert_eq!(None, canvas.get(3, 4));
+
+        *canvas.get_mut(1, 2).unwrap() = "home";
+        assert_eq!(Some(&"home"), canvas.get(1, 2));
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Marker {
+        glyph: char,
+        color: Option<AnsiColor>,
+    }
+
+    impl CellGlyph for Marker {
+        fn glyph(&self) -> char {
+            self.glyph
+        }
+
+        fn color(&self) -> Option<AnsiColor> {
+            self.color
+        }
+    }
+
+    #[test]
+    fn render_fills_overlaid_cells_and_blanks_the_rest() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..2, 0);
+        board.add_horizontal_line(0..2, 1);
+        board.add_vertical_line(0, 0..1);
+        board.add_vertical_line(2, 0..1);
+
+        let mut canvas: TurtleCanvas<Marker> = TurtleCanvas::new(board);
+        canvas.set(0, 0, Marker { glyph: 'T', color: None });
+
+        let rendered = canvas.render();
+        assert!(rendered.contains('T'));
+        assert!(!rendered.contains('\x1b'));
+        assert_eq!(Some("|T        |"), rendered.lines().nth(1));
+    }
+
+    #[test]
+    fn render_wraps_colored_cells_in_ansi_escapes() {
+        let mut board = TurtleBoard::new_strict();
+        board.add_horizontal_line(0..1, 0);
+        board.add_horizontal_line(0..1, 1);
+        board.add_vertical_line(0, 0..1);
+        board.add_vertical_line(1, 0..1);
+
+        let mut canvas: TurtleCanvas<Marker> = TurtleCanvas::new(board);
+        canvas.set(0, 0, Marker { glyph: 'R', color: Some(AnsiColor::Red) });
+
+        let rendered = canvas.render();
+        assert!(rendered.contains("\x1b[31mR"));
+        assert!(rendered.contains("\x1b[0m"));
+    }
+}